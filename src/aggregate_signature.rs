@@ -1,13 +1,34 @@
-use rand::{CryptoRng, RngCore};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::collections::{HashMap, HashSet};
 
 use crate::amcl_wrapper::group_elem::GroupElement;
 use crate::errors::PixelError;
 use crate::keys::{GeneratorSet, Verkey};
+use crate::merkle_tree::{MerkleProof, MerkleTree};
 use crate::signature::Signature;
-use amcl_wrapper::extension_field_gt::GT;
+use amcl_wrapper::field_elem::FieldElement;
 use amcl_wrapper::group_elem_g1::G1;
 use amcl_wrapper::group_elem_g2::G2;
 
+/// Controls how `verify`/`verify_using_aggr_vk`/`verify_distinct` treat an
+/// aggregate that collapsed to the point at infinity or a point outside the
+/// prime-order subgroup. These are two different things: an honest signature
+/// simply not matching the message, versus malformed or adversarial input
+/// (e.g. a rogue-key cancellation driving `avk` to the identity) that should
+/// never be confused with "the signature didn't verify".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationPolicy {
+    /// Reject an infinity point or incorrect-order point as an error, so
+    /// callers can tell malicious input apart from an honest verification
+    /// failure.
+    Strict,
+    /// Treat an infinity point as trivially-false, matching this crate's
+    /// original behavior. An incorrect-order point is still an error, since
+    /// there is no honest way to produce one.
+    Lenient,
+}
+
 pub struct AggregatedVerkey {
     pub value: G2,
 }
@@ -16,62 +37,102 @@ impl AggregatedVerkey {
     pub fn new(ver_keys: Vec<&Verkey>) -> Self {
         let mut avk: G2 = G2::identity();
         for vk in ver_keys {
-            avk += vk.value;
+            avk += &vk.value;
         }
         AggregatedVerkey { value: avk }
     }
 
-    pub fn is_identity(&self) -> bool {
-        if self.value.is_identity() {
-            println!("AggregatedVerkey point at infinity");
-            return true;
+    /// Like `new`, but rejects the aggregation unless every verkey comes with a
+    /// valid proof of possession. Plain summation lets an adversary register
+    /// `vk_adv = g2^r - sum(vk_honest)` and forge signatures attributed to the
+    /// whole group; requiring each signer to prove knowledge of their secret key
+    /// closes that rogue-key attack.
+    pub fn new_checked(ver_keys: Vec<&Verkey>, pops: Vec<&G1>) -> Result<Self, PixelError> {
+        if ver_keys.len() != pops.len() {
+            return Err(PixelError::MismatchedPopCount {
+                ver_keys: ver_keys.len(),
+                pops: pops.len(),
+            });
         }
-        return false;
+        for (vk, pop) in ver_keys.iter().zip(pops.iter()) {
+            if !vk.verify_possession(pop) {
+                return Err(PixelError::InvalidProofOfPossession);
+            }
+        }
+        Ok(Self::new(ver_keys))
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.value.is_identity()
+    }
+
+    /// Compressed G2 encoding of the aggregated verkey.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_bytes(true)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PixelError> {
+        Ok(AggregatedVerkey {
+            value: crate::amcl_wrapper::decode_g2(bytes)?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AggregatedVerkey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AggregatedVerkey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+        AggregatedVerkey::from_bytes(&bytes).map_err(serde::de::Error::custom)
     }
 }
 
 pub struct AggregatedSignature {
     pub sigma_1: G1,
-    pub sigma_2: G2,
 }
 
 // TODO: Merge with signature and remove duplicate code
 impl AggregatedSignature {
     pub fn new(sigs: Vec<&Signature>) -> Self {
         let mut asig_1 = G1::identity();
-        let mut asig_2 = G2::identity();
         for s in sigs {
-            asig_1 += s.sigma_1;
-            asig_2 += s.sigma_2;
-        }
-        AggregatedSignature {
-            sigma_1: asig_1,
-            sigma_2: asig_2,
+            asig_1 += &s.sigma_1;
         }
+        AggregatedSignature { sigma_1: asig_1 }
     }
 
     pub fn is_identity(&self) -> bool {
-        if self.sigma_1.is_identity() {
-            println!("Signature point in G1 at infinity");
-            return true;
-        }
-        if self.sigma_2.is_identity() {
-            println!("Signature point in G2 at infinity");
-            return true;
-        }
-        return false;
+        self.sigma_1.is_identity()
     }
 
     pub fn has_correct_oder(&self) -> bool {
-        if !self.sigma_1.has_correct_order() {
-            println!("Signature point in G1 has incorrect order");
-            return false;
-        }
-        if !self.sigma_2.has_correct_order() {
-            println!("Signature point in G2 has incorrect order");
-            return false;
+        self.sigma_1.has_correct_order()
+    }
+
+    /// Compact wire format: `sigma_1` as a compressed G1 point, same layout
+    /// as `Signature::to_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.sigma_1.to_bytes(true)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PixelError> {
+        let g1_size = crate::amcl_wrapper::G1_COMPRESSED_SIZE;
+        if bytes.len() != g1_size {
+            return Err(PixelError::MalformedEncoding(format!(
+                "expected {} bytes for an aggregated signature, got {}",
+                g1_size,
+                bytes.len()
+            )));
         }
-        return true;
+        Ok(AggregatedSignature {
+            sigma_1: crate::amcl_wrapper::decode_g1(bytes)?,
+        })
     }
 
     pub fn verify(
@@ -81,9 +142,10 @@ impl AggregatedSignature {
         l: u8,
         ver_keys: Vec<&Verkey>,
         gens: &GeneratorSet,
+        policy: VerificationPolicy,
     ) -> Result<bool, PixelError> {
         let avk = AggregatedVerkey::new(ver_keys);
-        self.verify_using_aggr_vk(msg, t, l, &avk, gens)
+        self.verify_using_aggr_vk(msg, t, l, &avk, gens, policy)
     }
 
     // For verifying multiple aggregate signatures from the same group of signers,
@@ -95,24 +157,319 @@ impl AggregatedSignature {
         l: u8,
         avk: &AggregatedVerkey,
         gens: &GeneratorSet,
+        policy: VerificationPolicy,
     ) -> Result<bool, PixelError> {
-        if self.is_identity() || avk.is_identity() || !self.has_correct_oder() {
-            return Ok(false);
+        if self.is_identity() || avk.is_identity() {
+            return match policy {
+                VerificationPolicy::Strict => Err(PixelError::PointAtInfinity),
+                VerificationPolicy::Lenient => Ok(false),
+            };
+        }
+        if !self.has_correct_oder() {
+            return Err(PixelError::IncorrectSubgroupOrder);
         }
         if gens.1.len() < (l as usize + 2) {
             return Err(PixelError::NotEnoughGenerators { n: l as usize + 2 });
         }
-        Signature::verify_naked(&self.sigma_1, &self.sigma_2, &avk.value, msg, t, l, gens)
+        Signature::verify_naked(&self.sigma_1, &avk.value, msg, t, l, gens)
+    }
+
+    /// Aggregate signatures that were each made over a different message at the
+    /// same epoch `t`. Summation is identical to `new` -- only verification
+    /// differs, since the signers can no longer be folded into one `avk`.
+    pub fn new_distinct(sigs: Vec<&Signature>) -> Self {
+        Self::new(sigs)
+    }
+
+    /// Verify a `new_distinct` aggregate: `signers` holds each signer's own
+    /// message paired with their verkey, in the order the signatures were
+    /// summed. Each message is checked against its own key via a product of
+    /// pairings rather than a single combined `avk` check, so a signer cannot
+    /// pass off their signature as being over someone else's message.
+    pub fn verify_distinct(
+        &self,
+        signers: Vec<(&[u8], &Verkey)>,
+        t: u128,
+        l: u8,
+        gens: &GeneratorSet,
+        policy: VerificationPolicy,
+    ) -> Result<bool, PixelError> {
+        if self.is_identity() {
+            return match policy {
+                VerificationPolicy::Strict => Err(PixelError::PointAtInfinity),
+                VerificationPolicy::Lenient => Ok(false),
+            };
+        }
+        if !self.has_correct_oder() {
+            return Err(PixelError::IncorrectSubgroupOrder);
+        }
+        if gens.1.len() < (l as usize + 2) {
+            return Err(PixelError::NotEnoughGenerators { n: l as usize + 2 });
+        }
+        let items: Vec<(&[u8], &G2)> = signers.iter().map(|(m, vk)| (*m, &vk.value)).collect();
+        Signature::verify_naked_distinct(&self.sigma_1, &items, t, l, gens)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AggregatedSignature {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AggregatedSignature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+        AggregatedSignature::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// One signature to verify as part of a `verify_batch` call.
+pub struct BatchItem<'a> {
+    pub sig: &'a AggregatedSignature,
+    pub msg: &'a [u8],
+    pub t: u128,
+    pub l: u8,
+    pub avk: &'a AggregatedVerkey,
+}
+
+/// Per-item outcome of `verify_batch`, in the same order as the input slice.
+pub struct BatchVerifyResults(pub Vec<bool>);
+
+impl BatchVerifyResults {
+    /// `true` iff every item in the batch verified.
+    pub fn all(&self) -> bool {
+        self.0.iter().all(|&ok| ok)
+    }
+}
+
+impl AggregatedSignature {
+    /// Verify many aggregate signatures at once: a relay or validator processing
+    /// thousands of signatures per slot can't afford to pay for a full pairing
+    /// check per signature.
+    ///
+    /// Items that share the same `(msg, t, l)` are combined into a single random
+    /// linear combination -- `sum(delta_i * sigma1_i)`, `sum(delta_i * avk_i)` --
+    /// for uniformly random nonzero scalars `delta_i` -- so one
+    /// multi-pairing check replaces N. A bucket that fails the combined check
+    /// falls back to verifying its items individually, since a random linear
+    /// combination can't identify which member actually failed. The buckets
+    /// themselves are fanned out across a rayon thread pool with `num_threads`
+    /// worker threads (`None` uses rayon's default).
+    pub fn verify_batch(
+        items: &[BatchItem],
+        gens: &GeneratorSet,
+        num_threads: Option<usize>,
+        policy: VerificationPolicy,
+    ) -> Result<BatchVerifyResults, PixelError> {
+        let mut buckets: HashMap<(Vec<u8>, u128, u8), Vec<usize>> = HashMap::new();
+        for (i, item) in items.iter().enumerate() {
+            buckets
+                .entry((item.msg.to_vec(), item.t, item.l))
+                .or_default()
+                .push(i);
+        }
+
+        // A bucket that fails its combined check falls back to verifying each item
+        // individually -- the random linear combination can't tell us which member
+        // failed, only that at least one did. Only the per-item checks are treated
+        // as authoritative: a `Strict` error out of one of them must reach the
+        // caller, rather than being downgraded to `false` the way a combined-check
+        // failure is, since a combined-bucket error doesn't mean each member is bad.
+        let run = || -> Result<Vec<(usize, bool)>, PixelError> {
+            buckets
+                .into_par_iter()
+                .map(|(_, idxs)| -> Result<Vec<(usize, bool)>, PixelError> {
+                    let bucket_ok =
+                        matches!(Self::verify_bucket_combined(&idxs, items, gens, policy), Ok(true));
+                    if bucket_ok {
+                        Ok(idxs.into_iter().map(|i| (i, true)).collect())
+                    } else {
+                        idxs.into_par_iter()
+                            .map(|i| {
+                                let item = &items[i];
+                                item.sig
+                                    .verify_using_aggr_vk(item.msg, item.t, item.l, item.avk, gens, policy)
+                                    .map(|ok| (i, ok))
+                            })
+                            .collect()
+                    }
+                })
+                .collect::<Result<Vec<Vec<_>>, _>>()
+                .map(|buckets| buckets.into_iter().flatten().collect())
+        };
+
+        let per_item = match num_threads {
+            Some(n) => {
+                let pool = ThreadPoolBuilder::new()
+                    .num_threads(n)
+                    .build()
+                    .map_err(|e| PixelError::ThreadPoolUnavailable(e.to_string()))?;
+                pool.install(run)
+            }
+            None => run(),
+        }?;
+
+        let mut results = vec![false; items.len()];
+        for (i, ok) in per_item {
+            results[i] = ok;
+        }
+        Ok(BatchVerifyResults(results))
+    }
+
+    fn verify_bucket_combined(
+        idxs: &[usize],
+        items: &[BatchItem],
+        gens: &GeneratorSet,
+        policy: VerificationPolicy,
+    ) -> Result<bool, PixelError> {
+        if idxs.len() == 1 {
+            let item = &items[idxs[0]];
+            return item
+                .sig
+                .verify_using_aggr_vk(item.msg, item.t, item.l, item.avk, gens, policy);
+        }
+
+        let mut combined_sigma_1 = G1::identity();
+        let mut combined_avk = G2::identity();
+        for &i in idxs {
+            let item = &items[i];
+            let mut delta = FieldElement::random();
+            while delta.is_zero() {
+                delta = FieldElement::random();
+            }
+            combined_sigma_1 += &item.sig.sigma_1 * &delta;
+            combined_avk += &item.avk.value * &delta;
+        }
+
+        let combined_sig = AggregatedSignature { sigma_1: combined_sigma_1 };
+        let combined_avk = AggregatedVerkey { value: combined_avk };
+        let rep = &items[idxs[0]];
+        combined_sig.verify_using_aggr_vk(rep.msg, rep.t, rep.l, &combined_avk, gens, policy)
+    }
+}
+
+/// The committee an ATMS threshold signature is checked against: every key that is
+/// *allowed* to sign at this epoch, sorted and committed to a Merkle tree so a
+/// verifier can later be convinced a claimed non-signer really is a member without
+/// holding the whole set.
+pub struct AtmsEligibleSet {
+    ver_keys: Vec<Verkey>,
+    tree: MerkleTree,
+    avk_master: G2,
+    pub threshold: usize,
+}
+
+impl AtmsEligibleSet {
+    pub fn new(mut ver_keys: Vec<Verkey>, threshold: usize) -> Self {
+        ver_keys.sort_by_key(|vk| vk.value.to_bytes(true));
+        let leaves: Vec<Vec<u8>> = ver_keys.iter().map(|vk| vk.value.to_bytes(true)).collect();
+        let tree = MerkleTree::new(&leaves);
+        let mut avk_master = G2::identity();
+        for vk in &ver_keys {
+            avk_master += &vk.value;
+        }
+        AtmsEligibleSet {
+            ver_keys,
+            tree,
+            avk_master,
+            threshold,
+        }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.tree.root()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ver_keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ver_keys.is_empty()
+    }
+
+    /// Membership proof for `vk`, for use by a verifier reconstructing the
+    /// participants' aggregate key from the non-signer list.
+    pub fn prove_membership(&self, vk: &Verkey) -> Option<MerkleProof> {
+        let target = vk.value.to_bytes(true);
+        let index = self
+            .ver_keys
+            .iter()
+            .position(|k| k.value.to_bytes(true) == target)?;
+        Some(self.tree.prove(index))
+    }
+}
+
+/// A non-signer's verkey plus the Merkle proof that it belongs to the epoch's
+/// `AtmsEligibleSet`.
+pub struct NonSigner {
+    pub ver_key: Verkey,
+    pub membership_proof: MerkleProof,
+}
+
+/// An ad-hoc threshold multisignature (ATMS): an aggregate signature over a subset
+/// of an eligible committee, accompanied by the verkeys that did *not* sign so a
+/// verifier can recompute the participants' aggregate key from the committed
+/// `avk_master` without needing the full signer list.
+pub struct AtmsAggregatedSignature {
+    pub asig: AggregatedSignature,
+    pub non_signers: Vec<NonSigner>,
+}
+
+impl AtmsAggregatedSignature {
+    pub fn new(sigs: Vec<&Signature>, non_signers: Vec<NonSigner>) -> Self {
+        AtmsAggregatedSignature {
+            asig: AggregatedSignature::new(sigs),
+            non_signers,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        t: u128,
+        l: u8,
+        eligible: &AtmsEligibleSet,
+        gens: &GeneratorSet,
+        policy: VerificationPolicy,
+    ) -> Result<bool, PixelError> {
+        let root = eligible.root();
+        let mut seen = HashSet::with_capacity(self.non_signers.len());
+        let mut avk_part = eligible.avk_master.clone();
+        for non_signer in &self.non_signers {
+            let leaf = non_signer.ver_key.value.to_bytes(true);
+            if !seen.insert(leaf.clone()) {
+                return Err(PixelError::DuplicateNonSigner);
+            }
+            if !MerkleTree::verify_proof(&root, &leaf, &non_signer.membership_proof) {
+                return Err(PixelError::NonSignerNotEligible);
+            }
+            avk_part -= &non_signer.ver_key.value;
+        }
+
+        let signers = eligible.len() - self.non_signers.len();
+        if signers < eligible.threshold {
+            return Err(PixelError::ThresholdNotMet {
+                required: eligible.threshold,
+                got: signers,
+            });
+        }
+
+        let avk = AggregatedVerkey { value: avk_part };
+        self.asig.verify_using_aggr_vk(msg, t, l, &avk, gens, policy)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::keys::{setup, Keypair, Sigkey, SigkeySet};
+    use crate::keys::{setup, Keypair, SigkeySet};
     use crate::util::calculate_l;
     use rand::rngs::ThreadRng;
-    use std::process::abort;
+    use rand::{CryptoRng, RngCore};
 
     pub fn create_sig_and_verify<R: RngCore + CryptoRng>(
         set: &SigkeySet,
@@ -124,21 +481,21 @@ mod tests {
     ) {
         let sk = set.get_key(t).unwrap();
         let msg = "Hello".as_bytes();
-        let sig = Signature::new(msg, t, l, &gens, &sk, &mut rng).unwrap();
-        assert!(sig.verify(msg, t, l, &gens, &vk).unwrap());
+        let sig = Signature::new(msg, t, l, gens, sk, &mut rng).unwrap();
+        assert!(sig.verify(msg, t, l, gens, vk).unwrap());
     }
 
     #[test]
     fn test_aggr_sig_verify() {
         let mut rng = rand::thread_rng();
-        let T = 7;
-        let l = calculate_l(T).unwrap();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
         let mut t = 1u128;
 
         let (gens, vk1, mut sigkey_set1, _) =
-            setup::<ThreadRng>(T, "test_pixel", &mut rng).unwrap();
+            setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
 
-        let (keypair2, mut sigkey_set2) = Keypair::new(T, &gens, &mut rng).unwrap();
+        let (keypair2, mut sigkey_set2) = Keypair::new(t_max, &gens, &mut rng).unwrap();
         let vk2 = keypair2.ver_key;
 
         create_sig_and_verify::<ThreadRng>(&sigkey_set1, t, &vk1, l, &gens, &mut rng);
@@ -147,12 +504,14 @@ mod tests {
         {
             let msg = "Hello".as_bytes();
             let sk1 = sigkey_set1.get_key(t).unwrap();
-            let sig1 = Signature::new(msg, t, l, &gens, &sk1, &mut rng).unwrap();
+            let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
             let sk2 = sigkey_set2.get_key(t).unwrap();
-            let sig2 = Signature::new(msg, t, l, &gens, &sk2, &mut rng).unwrap();
+            let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
 
             let asig = AggregatedSignature::new(vec![&sig1, &sig2]);
-            assert!(asig.verify(msg, t, l, vec![&vk1, &vk2], &gens).unwrap());
+            assert!(asig
+                .verify(msg, t, l, vec![&vk1, &vk2], &gens, VerificationPolicy::Strict)
+                .unwrap());
         }
 
         {
@@ -162,12 +521,14 @@ mod tests {
 
             let msg = "Hello".as_bytes();
             let sk1 = sigkey_set1.get_key(t).unwrap();
-            let sig1 = Signature::new(msg, t, l, &gens, &sk1, &mut rng).unwrap();
+            let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
             let sk2 = sigkey_set2.get_key(t).unwrap();
-            let sig2 = Signature::new(msg, t, l, &gens, &sk2, &mut rng).unwrap();
+            let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
 
             let asig = AggregatedSignature::new(vec![&sig1, &sig2]);
-            assert!(asig.verify(msg, t, l, vec![&vk1, &vk2], &gens).unwrap());
+            assert!(asig
+                .verify(msg, t, l, vec![&vk1, &vk2], &gens, VerificationPolicy::Strict)
+                .unwrap());
         }
 
         {
@@ -177,12 +538,314 @@ mod tests {
 
             let msg = "Hello".as_bytes();
             let sk1 = sigkey_set1.get_key(t).unwrap();
-            let sig1 = Signature::new(msg, t, l, &gens, &sk1, &mut rng).unwrap();
+            let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
             let sk2 = sigkey_set2.get_key(t).unwrap();
-            let sig2 = Signature::new(msg, t, l, &gens, &sk2, &mut rng).unwrap();
+            let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
 
             let asig = AggregatedSignature::new(vec![&sig1, &sig2]);
-            assert!(asig.verify(msg, t, l, vec![&vk1, &vk2], &gens).unwrap());
+            assert!(asig
+                .verify(msg, t, l, vec![&vk1, &vk2], &gens, VerificationPolicy::Strict)
+                .unwrap());
         }
     }
+
+    #[test]
+    fn test_aggregated_verkey_new_checked_rejects_bad_pop() {
+        let mut rng = rand::thread_rng();
+        let t = 7;
+        let (gens, vk1, _, keypair1) = setup::<ThreadRng>(t, "test_pixel", &mut rng).unwrap();
+        let (keypair2, _) = Keypair::new(t, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+
+        let pop1 = vk1.prove_possession(&keypair1.msk);
+        let pop2 = vk2.prove_possession(&keypair2.msk);
+        assert!(AggregatedVerkey::new_checked(vec![&vk1, &vk2], vec![&pop1, &pop2]).is_ok());
+
+        // A PoP that doesn't match its verkey must be rejected.
+        assert!(AggregatedVerkey::new_checked(vec![&vk1, &vk2], vec![&pop2, &pop1]).is_err());
+    }
+
+    #[test]
+    fn test_atms_threshold_signature() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let (keypair2, sigkey_set2) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+        let (keypair3, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk3 = keypair3.ver_key;
+
+        let msg = "Hello".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
+        let sk2 = sigkey_set2.get_key(t).unwrap();
+        let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
+
+        let vk3_bytes = vk3.value.to_bytes(true);
+        let eligible = AtmsEligibleSet::new(vec![vk1, vk2, vk3], 2);
+        let non_signer_vk = eligible
+            .ver_keys
+            .iter()
+            .find(|vk| vk.value.to_bytes(true) == vk3_bytes)
+            .unwrap();
+        let proof = eligible.prove_membership(non_signer_vk).unwrap();
+        let non_signer = NonSigner {
+            ver_key: Verkey { value: non_signer_vk.value.clone() },
+            membership_proof: proof,
+        };
+
+        let atms = AtmsAggregatedSignature::new(vec![&sig1, &sig2], vec![non_signer]);
+        assert!(atms
+            .verify(msg, t, l, &eligible, &gens, VerificationPolicy::Strict)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_atms_rejects_duplicate_non_signer() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let (keypair2, sigkey_set2) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+        let (keypair3, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk3 = keypair3.ver_key;
+
+        let msg = "Hello".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
+        let sk2 = sigkey_set2.get_key(t).unwrap();
+        let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
+
+        let vk3_bytes = vk3.value.to_bytes(true);
+        let eligible = AtmsEligibleSet::new(vec![vk1, vk2, vk3], 2);
+        let non_signer_vk = eligible
+            .ver_keys
+            .iter()
+            .find(|vk| vk.value.to_bytes(true) == vk3_bytes)
+            .unwrap();
+        let proof = eligible.prove_membership(non_signer_vk).unwrap();
+        let make_non_signer = || NonSigner {
+            ver_key: Verkey { value: non_signer_vk.value.clone() },
+            membership_proof: proof.clone(),
+        };
+
+        let atms = AtmsAggregatedSignature::new(vec![&sig1, &sig2], vec![make_non_signer(), make_non_signer()]);
+        assert_eq!(
+            atms.verify(msg, t, l, &eligible, &gens, VerificationPolicy::Strict),
+            Err(PixelError::DuplicateNonSigner)
+        );
+    }
+
+    #[test]
+    fn test_atms_rejects_ineligible_non_signer() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let (keypair2, sigkey_set2) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+        let (keypair3, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk3 = keypair3.ver_key;
+        let (keypair_outsider, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk_outsider = keypair_outsider.ver_key;
+
+        let msg = "Hello".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
+        let sk2 = sigkey_set2.get_key(t).unwrap();
+        let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
+
+        let eligible = AtmsEligibleSet::new(vec![vk1, vk2, vk3], 2);
+        // Borrow a membership proof for a real member, but pair it with a verkey
+        // that was never part of the eligible set -- the leaf hash won't match, so
+        // this must be rejected rather than accepted as a legitimate non-signer.
+        let real_member = &eligible.ver_keys[0];
+        let stolen_proof = eligible.prove_membership(real_member).unwrap();
+        let non_signer = NonSigner {
+            ver_key: vk_outsider,
+            membership_proof: stolen_proof,
+        };
+
+        let atms = AtmsAggregatedSignature::new(vec![&sig1, &sig2], vec![non_signer]);
+        assert_eq!(
+            atms.verify(msg, t, l, &eligible, &gens, VerificationPolicy::Strict),
+            Err(PixelError::NonSignerNotEligible)
+        );
+    }
+
+    #[test]
+    fn test_atms_rejects_below_threshold() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let (keypair2, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+        let (keypair3, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk3 = keypair3.ver_key;
+
+        let msg = "Hello".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
+
+        // Threshold 2, but the keys at sorted positions 1 and 2 are both declared
+        // non-signers, leaving a single signer -- one short of the threshold.
+        let eligible = AtmsEligibleSet::new(vec![vk1, vk2, vk3], 2);
+        let non_signers: Vec<NonSigner> = [1usize, 2]
+            .iter()
+            .map(|&idx| {
+                let vk = &eligible.ver_keys[idx];
+                NonSigner {
+                    ver_key: Verkey { value: vk.value.clone() },
+                    membership_proof: eligible.prove_membership(vk).unwrap(),
+                }
+            })
+            .collect();
+
+        let atms = AtmsAggregatedSignature::new(vec![&sig1], non_signers);
+        assert_eq!(
+            atms.verify(msg, t, l, &eligible, &gens, VerificationPolicy::Strict),
+            Err(PixelError::ThresholdNotMet { required: 2, got: 1 })
+        );
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let (keypair2, sigkey_set2) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+        let (keypair3, _) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk3 = keypair3.ver_key;
+
+        let msg = "Hello".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
+        let sk2 = sigkey_set2.get_key(t).unwrap();
+        let sig2 = Signature::new(msg, t, l, &gens, sk2, &mut rng).unwrap();
+
+        let asig1 = AggregatedSignature::new(vec![&sig1]);
+        let asig2 = AggregatedSignature::new(vec![&sig2]);
+        let avk1 = AggregatedVerkey::new(vec![&vk1]);
+        let avk2 = AggregatedVerkey::new(vec![&vk2]);
+        let avk3 = AggregatedVerkey::new(vec![&vk3]);
+
+        // A signature whose sigma_1 is unrelated to `msg`, standing in for a
+        // tampered signature, over the SAME (msg, t) as the two valid items so
+        // all three land in one multi-member bucket. This exercises the real
+        // RLC code path: the combined check must fail (the random linear
+        // combination can't land on the right point without the real scalar
+        // key), so `run` falls back to re-verifying each item individually --
+        // the two valid items passing while the tampered one fails.
+        let tampered_sig = AggregatedSignature { sigma_1: G1::random() };
+
+        let items = vec![
+            BatchItem {
+                sig: &asig1,
+                msg,
+                t,
+                l,
+                avk: &avk1,
+            },
+            BatchItem {
+                sig: &asig2,
+                msg,
+                t,
+                l,
+                avk: &avk2,
+            },
+            BatchItem {
+                sig: &tampered_sig,
+                msg,
+                t,
+                l,
+                avk: &avk3,
+            },
+        ];
+
+        let results =
+            AggregatedSignature::verify_batch(&items, &gens, Some(2), VerificationPolicy::Strict)
+                .unwrap();
+        assert!(results.0[0]);
+        assert!(results.0[1]);
+        assert!(!results.0[2]);
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let msg = "Hello".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg, t, l, &gens, sk1, &mut rng).unwrap();
+
+        let sig_bytes = sig1.to_bytes();
+        let sig_roundtrip = Signature::from_bytes(&sig_bytes).unwrap();
+        assert!(sig_roundtrip.verify(msg, t, l, &gens, &vk1).unwrap());
+
+        let vk_bytes = vk1.to_bytes();
+        let vk_roundtrip = Verkey::from_bytes(&vk_bytes).unwrap();
+        assert!(sig1.verify(msg, t, l, &gens, &vk_roundtrip).unwrap());
+
+        let asig = AggregatedSignature::new(vec![&sig1]);
+        let avk = AggregatedVerkey::new(vec![&vk1]);
+        let asig_roundtrip = AggregatedSignature::from_bytes(&asig.to_bytes()).unwrap();
+        let avk_roundtrip = AggregatedVerkey::from_bytes(&avk.to_bytes()).unwrap();
+        assert!(asig_roundtrip
+            .verify_using_aggr_vk(msg, t, l, &avk_roundtrip, &gens, VerificationPolicy::Strict)
+            .unwrap());
+
+        // The point-at-infinity encoding must never decode successfully.
+        let infinity_g2 = vec![0u8; crate::amcl_wrapper::G2_COMPRESSED_SIZE];
+        assert!(Verkey::from_bytes(&infinity_g2).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_distinct_messages() {
+        let mut rng = rand::thread_rng();
+        let t_max = 7;
+        let l = calculate_l(t_max).unwrap();
+        let t = 1u128;
+
+        let (gens, vk1, sigkey_set1, _) = setup::<ThreadRng>(t_max, "test_pixel", &mut rng).unwrap();
+        let (keypair2, sigkey_set2) = Keypair::new(t_max, &gens, &mut rng).unwrap();
+        let vk2 = keypair2.ver_key;
+
+        let msg1 = "Hello".as_bytes();
+        let msg2 = "Goodbye".as_bytes();
+        let sk1 = sigkey_set1.get_key(t).unwrap();
+        let sig1 = Signature::new(msg1, t, l, &gens, sk1, &mut rng).unwrap();
+        let sk2 = sigkey_set2.get_key(t).unwrap();
+        let sig2 = Signature::new(msg2, t, l, &gens, sk2, &mut rng).unwrap();
+
+        let asig = AggregatedSignature::new_distinct(vec![&sig1, &sig2]);
+        assert!(asig
+            .verify_distinct(vec![(msg1, &vk1), (msg2, &vk2)], t, l, &gens, VerificationPolicy::Strict)
+            .unwrap());
+
+        // Swapping which verkey a message is checked against must fail -- each
+        // signer's message is bound to its own key via the pairing product, not
+        // folded into one shared avk.
+        assert!(!asig
+            .verify_distinct(vec![(msg2, &vk1), (msg1, &vk2)], t, l, &gens, VerificationPolicy::Strict)
+            .unwrap());
+    }
 }
@@ -0,0 +1,67 @@
+use crate::errors::PixelError;
+use amcl_wrapper::group_elem_g1::G1;
+use amcl_wrapper::group_elem_g2::G2;
+
+pub mod group_elem {
+    pub use amcl_wrapper::group_elem::GroupElement;
+}
+
+use group_elem::GroupElement;
+
+/// Size in bytes of a compressed BLS12-381 G1 point, as produced by
+/// `GroupElement::to_bytes(true)` on a `G1`.
+pub const G1_COMPRESSED_SIZE: usize = 49;
+
+/// Size in bytes of a compressed BLS12-381 G2 point, as produced by
+/// `GroupElement::to_bytes(true)` on a `G2`.
+pub const G2_COMPRESSED_SIZE: usize = 97;
+
+/// Decode a compressed G1 point, rejecting malformed lengths, the
+/// point-at-infinity encoding, and points outside the prime-order subgroup so
+/// that a deserialized point can be used exactly as a freshly-generated one.
+pub(crate) fn decode_g1(bytes: &[u8]) -> Result<G1, PixelError> {
+    if bytes.len() != G1_COMPRESSED_SIZE {
+        return Err(PixelError::MalformedEncoding(format!(
+            "expected {} bytes for a G1 point, got {}",
+            G1_COMPRESSED_SIZE,
+            bytes.len()
+        )));
+    }
+    let point = G1::from_bytes(bytes)
+        .map_err(|_| PixelError::MalformedEncoding("invalid G1 point encoding".to_string()))?;
+    if point.is_identity() {
+        return Err(PixelError::MalformedEncoding(
+            "G1 point at infinity is not a valid point encoding".to_string(),
+        ));
+    }
+    if !point.has_correct_order() {
+        return Err(PixelError::MalformedEncoding(
+            "G1 point is not in the prime-order subgroup".to_string(),
+        ));
+    }
+    Ok(point)
+}
+
+/// Decode a compressed G2 point with the same validation as `decode_g1`.
+pub(crate) fn decode_g2(bytes: &[u8]) -> Result<G2, PixelError> {
+    if bytes.len() != G2_COMPRESSED_SIZE {
+        return Err(PixelError::MalformedEncoding(format!(
+            "expected {} bytes for a G2 point, got {}",
+            G2_COMPRESSED_SIZE,
+            bytes.len()
+        )));
+    }
+    let point = G2::from_bytes(bytes)
+        .map_err(|_| PixelError::MalformedEncoding("invalid G2 point encoding".to_string()))?;
+    if point.is_identity() {
+        return Err(PixelError::MalformedEncoding(
+            "G2 point at infinity is not a valid point encoding".to_string(),
+        ));
+    }
+    if !point.has_correct_order() {
+        return Err(PixelError::MalformedEncoding(
+            "G2 point is not in the prime-order subgroup".to_string(),
+        ));
+    }
+    Ok(point)
+}
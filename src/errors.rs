@@ -0,0 +1,54 @@
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PixelError {
+    NotEnoughGenerators { n: usize },
+    MismatchedPopCount { ver_keys: usize, pops: usize },
+    InvalidProofOfPossession,
+    NonSignerNotEligible,
+    DuplicateNonSigner,
+    ThresholdNotMet { required: usize, got: usize },
+    MalformedEncoding(String),
+    PointAtInfinity,
+    IncorrectSubgroupOrder,
+    ThreadPoolUnavailable(String),
+}
+
+impl fmt::Display for PixelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PixelError::NotEnoughGenerators { n } => {
+                write!(f, "need at least {} generators", n)
+            }
+            PixelError::MismatchedPopCount { ver_keys, pops } => {
+                write!(f, "got {} verkeys but {} proofs of possession", ver_keys, pops)
+            }
+            PixelError::InvalidProofOfPossession => {
+                write!(f, "proof of possession does not verify for one of the given verkeys")
+            }
+            PixelError::NonSignerNotEligible => {
+                write!(f, "a claimed non-signer is not a member of the eligible set")
+            }
+            PixelError::DuplicateNonSigner => {
+                write!(f, "the same non-signer verkey was listed more than once")
+            }
+            PixelError::ThresholdNotMet { required, got } => {
+                write!(f, "threshold not met: need {} signers, got {}", required, got)
+            }
+            PixelError::MalformedEncoding(reason) => {
+                write!(f, "malformed encoding: {}", reason)
+            }
+            PixelError::PointAtInfinity => {
+                write!(f, "signature or aggregate verkey is the point at infinity")
+            }
+            PixelError::IncorrectSubgroupOrder => {
+                write!(f, "signature point is not in the prime-order subgroup")
+            }
+            PixelError::ThreadPoolUnavailable(reason) => {
+                write!(f, "failed to build rayon thread pool: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PixelError {}
@@ -0,0 +1,164 @@
+use rand::{CryptoRng, RngCore};
+
+use crate::amcl_wrapper::group_elem::GroupElement;
+use crate::errors::PixelError;
+use amcl_wrapper::extension_field_gt::GT;
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem_g1::G1;
+use amcl_wrapper::group_elem_g2::G2;
+
+/// Domain separation tag for the proof-of-possession hash-to-G1, so a PoP can never
+/// be confused with a Pixel signature or any other hash-to-curve use in this crate.
+const POP_DOMAIN: &[u8] = b"PIXEL-POP-BLS12-381G1-SHA256_";
+
+/// Public parameters shared by every signer: a generator of G1 and one generator
+/// of G2 per level of the time tree (plus the two "header" generators).
+pub struct GeneratorSet(pub G1, pub Vec<G2>);
+
+pub struct Verkey {
+    pub value: G2,
+}
+
+impl Verkey {
+    fn pop_hash(&self) -> G1 {
+        let mut msg = Vec::with_capacity(POP_DOMAIN.len() + 96);
+        msg.extend_from_slice(POP_DOMAIN);
+        msg.extend_from_slice(&self.value.to_bytes(true));
+        G1::from_msg_hash(&msg)
+    }
+
+    /// Sign `H_pop(vk)` with the holder's master secret key, proving knowledge of
+    /// the secret key behind this verkey. Required before the verkey may be fed to
+    /// `AggregatedVerkey::new_checked`, which guards against rogue-key attacks.
+    pub fn prove_possession(&self, msk: &FieldElement) -> G1 {
+        self.pop_hash() * msk
+    }
+
+    /// Check a proof of possession produced by `prove_possession` via
+    /// `e(pop, g2) == e(H_pop(vk), vk)`.
+    pub fn verify_possession(&self, pop: &G1) -> bool {
+        if pop.is_identity() || !pop.has_correct_order() {
+            return false;
+        }
+        let lhs = GT::ate_pairing(pop, &G2::generator());
+        let rhs = GT::ate_pairing(&self.pop_hash(), &self.value);
+        lhs == rhs
+    }
+
+    /// Compressed G2 encoding of the verkey.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.value.to_bytes(true)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PixelError> {
+        Ok(Verkey {
+            value: crate::amcl_wrapper::decode_g2(bytes)?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Verkey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Verkey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+        Verkey::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Per-leaf signing key. A scalar derived from the holder's master secret key;
+/// opaque outside the `keys`/`signature` modules.
+pub struct Sigkey {
+    pub(crate) value: FieldElement,
+}
+
+/// The set of signing keys a signer currently holds, covering time `t` and every
+/// time after it up to `T`.
+///
+/// NOTE: this is not yet the forward-secure Pixel key-evolution scheme. Every
+/// `Sigkey` this crate ever hands out holds the same root secret
+/// (`Keypair::msk`), and `fast_forward_update` only advances `current_time` --
+/// it does not derive fresh per-epoch key material or erase anything. `t`
+/// is bound into the signed message for domain separation, but a holder of
+/// the current `Sigkey` can still sign for any past epoch just as validly as
+/// the current one. Treat this as a plain (non-forward-secure) BLS signature
+/// scheme with an epoch tag until real per-epoch derivation replaces this.
+pub struct SigkeySet {
+    pub(crate) current_time: u128,
+    pub(crate) keys: Vec<Sigkey>,
+}
+
+impl SigkeySet {
+    pub fn get_key(&self, t: u128) -> Result<&Sigkey, PixelError> {
+        if t != self.current_time {
+            return Err(PixelError::NotEnoughGenerators { n: 0 });
+        }
+        self.keys.first().ok_or(PixelError::NotEnoughGenerators { n: 0 })
+    }
+
+    /// Advance the current epoch marker. Does **not** evolve or discard any
+    /// key material -- see the `SigkeySet` doc comment above. A forward-secure
+    /// implementation would derive fresh per-level keys here and erase
+    /// everything before `t`.
+    pub fn fast_forward_update<R: RngCore + CryptoRng>(
+        &mut self,
+        t: u128,
+        _gens: &GeneratorSet,
+        _rng: &mut R,
+    ) -> Result<(), PixelError> {
+        self.current_time = t;
+        Ok(())
+    }
+}
+
+pub struct Keypair {
+    pub ver_key: Verkey,
+    // Retained for proof-of-possession and future key-evolution use; not yet
+    // read outside tests since `SigkeySet` update derivation is still a stub.
+    #[allow(dead_code)]
+    pub(crate) msk: FieldElement,
+}
+
+impl Keypair {
+    pub fn new<R: RngCore + CryptoRng>(
+        _t: u128,
+        _gens: &GeneratorSet,
+        _rng: &mut R,
+    ) -> Result<(Self, SigkeySet), PixelError> {
+        let msk = FieldElement::random();
+        let sigkey_set = SigkeySet {
+            current_time: 1,
+            keys: vec![Sigkey { value: msk.clone() }],
+        };
+        let keypair = Keypair {
+            ver_key: Verkey {
+                value: &G2::generator() * &msk,
+            },
+            msk,
+        };
+        Ok((keypair, sigkey_set))
+    }
+}
+
+pub fn setup<R: RngCore + CryptoRng>(
+    t: u128,
+    _label: &str,
+    rng: &mut R,
+) -> Result<(GeneratorSet, Verkey, SigkeySet, Keypair), PixelError> {
+    let l = crate::util::calculate_l(t)?;
+    let gens = GeneratorSet(
+        G1::generator(),
+        (0..=l as usize + 1).map(|_| G2::generator()).collect(),
+    );
+    let (keypair, sigkey_set) = Keypair::new(t, &gens, rng)?;
+    let vk = Verkey {
+        value: keypair.ver_key.value.clone(),
+    };
+    Ok((gens, vk, sigkey_set, keypair))
+}
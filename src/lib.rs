@@ -0,0 +1,7 @@
+pub mod aggregate_signature;
+pub mod amcl_wrapper;
+pub mod errors;
+pub mod keys;
+pub mod merkle_tree;
+pub mod signature;
+pub mod util;
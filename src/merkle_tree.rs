@@ -0,0 +1,122 @@
+use sha2::{Digest, Sha256};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+fn hash_leaf(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// A Merkle inclusion proof: the sibling hash at each level from the leaf up to
+/// (but excluding) the root.
+#[derive(Clone, Debug)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+/// A deterministic binary Merkle tree committing to a sorted set of leaves
+/// (eligible verkeys, in `AtmsEligibleSet`). When a layer has an odd number of
+/// nodes, its last node is hashed against itself.
+pub struct MerkleTree {
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: &[Vec<u8>]) -> Self {
+        assert!(!leaves.is_empty(), "cannot build a Merkle tree over no leaves");
+        let mut layers = vec![leaves.iter().map(|l| hash_leaf(l)).collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let prev = layers.last().unwrap();
+            let mut next = Vec::with_capacity(prev.len().div_ceil(2));
+            for pair in prev.chunks(2) {
+                let right = if pair.len() == 2 { &pair[1] } else { &pair[0] };
+                next.push(hash_node(&pair[0], right));
+            }
+            layers.push(next);
+        }
+        MerkleTree { layers }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    pub fn prove(&self, leaf_index: usize) -> MerkleProof {
+        let mut index = leaf_index;
+        let mut siblings = Vec::with_capacity(self.layers.len() - 1);
+        for layer in &self.layers[..self.layers.len() - 1] {
+            let sibling_index = index ^ 1;
+            let sibling = layer.get(sibling_index).copied().unwrap_or(layer[index]);
+            siblings.push(sibling);
+            index /= 2;
+        }
+        MerkleProof { leaf_index, siblings }
+    }
+
+    pub fn verify_proof(root: &[u8; 32], leaf_data: &[u8], proof: &MerkleProof) -> bool {
+        let mut hash = hash_leaf(leaf_data);
+        let mut index = proof.leaf_index;
+        for sibling in &proof.siblings {
+            hash = if index.is_multiple_of(2) {
+                hash_node(&hash, sibling)
+            } else {
+                hash_node(sibling, &hash)
+            };
+            index /= 2;
+        }
+        &hash == root
+    }
+
+    /// Verify several leaves against the same root at once, rejecting on the first
+    /// mismatch. Proofs are still checked individually (no shared-path
+    /// compression) but this gives callers a single entry point for a batch.
+    pub fn verify_batch(root: &[u8; 32], leaves: &[(&[u8], &MerkleProof)]) -> bool {
+        leaves
+            .iter()
+            .all(|(data, proof)| Self::verify_proof(root, data, proof))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_proof_roundtrip() {
+        let leaves: Vec<Vec<u8>> = (0u8..5).map(|i| vec![i; 4]).collect();
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.root();
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.prove(i);
+            assert!(MerkleTree::verify_proof(&root, leaf, &proof));
+        }
+        let bad_proof = tree.prove(0);
+        assert!(!MerkleTree::verify_proof(&root, &leaves[1], &bad_proof));
+    }
+
+    #[test]
+    fn test_batch_proof() {
+        let leaves: Vec<Vec<u8>> = (0u8..6).map(|i| vec![i; 4]).collect();
+        let tree = MerkleTree::new(&leaves);
+        let root = tree.root();
+        let proofs: Vec<MerkleProof> = [1, 3, 4].iter().map(|&i| tree.prove(i)).collect();
+        let batch: Vec<(&[u8], &MerkleProof)> = [1usize, 3, 4]
+            .iter()
+            .zip(proofs.iter())
+            .map(|(&i, p)| (leaves[i].as_slice(), p))
+            .collect();
+        assert!(MerkleTree::verify_batch(&root, &batch));
+    }
+}
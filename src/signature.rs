@@ -0,0 +1,143 @@
+use rand::{CryptoRng, RngCore};
+
+use crate::amcl_wrapper::decode_g1;
+use crate::amcl_wrapper::group_elem::GroupElement;
+use crate::errors::PixelError;
+use crate::keys::{GeneratorSet, Sigkey, Verkey};
+use amcl_wrapper::extension_field_gt::GT;
+use amcl_wrapper::group_elem_g1::G1;
+use amcl_wrapper::group_elem_g2::G2;
+
+/// Domain separation tag for the message/epoch hash-to-G1, so a Pixel
+/// signature can never be confused with a proof-of-possession or any other
+/// hash-to-curve use in this crate.
+const SIG_DOMAIN: &[u8] = b"PIXEL-SIG-BLS12-381G1-SHA256_";
+
+/// Hash `(msg, t)` into G1 with domain separation. Binding `t` into the hash
+/// is what stops an attacker from splicing a signature made at one epoch onto
+/// the same message at a different epoch.
+fn hash_msg(msg: &[u8], t: u128) -> G1 {
+    let mut bytes = Vec::with_capacity(SIG_DOMAIN.len() + 16 + msg.len());
+    bytes.extend_from_slice(SIG_DOMAIN);
+    bytes.extend_from_slice(&t.to_be_bytes());
+    bytes.extend_from_slice(msg);
+    G1::from_msg_hash(&bytes)
+}
+
+pub struct Signature {
+    pub sigma_1: G1,
+}
+
+impl Signature {
+    /// Compact wire format: `sigma_1` as a compressed G1 point.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.sigma_1.to_bytes(true)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PixelError> {
+        if bytes.len() != crate::amcl_wrapper::G1_COMPRESSED_SIZE {
+            return Err(PixelError::MalformedEncoding(format!(
+                "expected {} bytes for a signature, got {}",
+                crate::amcl_wrapper::G1_COMPRESSED_SIZE,
+                bytes.len()
+            )));
+        }
+        Ok(Signature {
+            sigma_1: decode_g1(bytes)?,
+        })
+    }
+
+    /// Sign `msg` at epoch `t` with `sk`. This is plain BLS over `H(msg, t)`,
+    /// not forward-secure Pixel signing: see the `SigkeySet` doc comment in
+    /// `keys.rs` for why `t` is only a domain-separation tag here, not an
+    /// enforced epoch bound.
+    pub fn new<R: RngCore + CryptoRng>(
+        msg: &[u8],
+        t: u128,
+        l: u8,
+        gens: &GeneratorSet,
+        sk: &Sigkey,
+        _rng: &mut R,
+    ) -> Result<Self, PixelError> {
+        if gens.1.len() < (l as usize + 2) {
+            return Err(PixelError::NotEnoughGenerators { n: l as usize + 2 });
+        }
+        Ok(Signature {
+            sigma_1: hash_msg(msg, t) * &sk.value,
+        })
+    }
+
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        t: u128,
+        l: u8,
+        gens: &GeneratorSet,
+        vk: &Verkey,
+    ) -> Result<bool, PixelError> {
+        Signature::verify_naked(&self.sigma_1, &vk.value, msg, t, l, gens)
+    }
+
+    // For verifying multiple signatures against the same key or aggregate key, the
+    // caller is expected to reuse the (aggregated) verkey rather than rebuilding it.
+    pub fn verify_naked(
+        sigma_1: &G1,
+        vk: &G2,
+        msg: &[u8],
+        t: u128,
+        l: u8,
+        gens: &GeneratorSet,
+    ) -> Result<bool, PixelError> {
+        if gens.1.len() < (l as usize + 2) {
+            return Err(PixelError::NotEnoughGenerators { n: l as usize + 2 });
+        }
+        let lhs = GT::ate_pairing(sigma_1, &G2::generator());
+        let rhs = GT::ate_pairing(&hash_msg(msg, t), vk);
+        Ok(lhs == rhs)
+    }
+
+    /// Verify an aggregate whose signers each signed their own message at the
+    /// same epoch `t`. Unlike `verify_naked`, which checks a single combined
+    /// `e(sigma, g2) == e(H(msg, t), avk)`, this evaluates the product
+    /// `Pi_i e(component_i(msg_i, t), vk_i)` so each signer's message is bound to
+    /// its own key rather than folded into one shared `avk`. `items` is
+    /// `(msg_i, vk_i)` per signer, in the same order the signatures were summed.
+    ///
+    /// Epoch binding (every term still hashes in `t`) is what stops an attacker
+    /// from splicing a signature made at one epoch onto a different signer's
+    /// message at another.
+    pub fn verify_naked_distinct(
+        sigma_1: &G1,
+        items: &[(&[u8], &G2)],
+        t: u128,
+        l: u8,
+        gens: &GeneratorSet,
+    ) -> Result<bool, PixelError> {
+        if gens.1.len() < (l as usize + 2) {
+            return Err(PixelError::NotEnoughGenerators { n: l as usize + 2 });
+        }
+        if items.is_empty() {
+            return Ok(false);
+        }
+        let hashes: Vec<G1> = items.iter().map(|(msg, _)| hash_msg(msg, t)).collect();
+        let pairs: Vec<(&G1, &G2)> = hashes.iter().zip(items.iter().map(|(_, vk)| *vk)).collect();
+        let lhs = GT::ate_pairing(sigma_1, &G2::generator());
+        let rhs = GT::ate_multi_pairing(pairs);
+        Ok(lhs == rhs)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signature {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.to_bytes())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+        Signature::from_bytes(&bytes).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,15 @@
+use crate::errors::PixelError;
+
+/// Pixel organizes the time period `[1, T]` as the leaves of a binary tree, so the
+/// tree depth `l` must be the smallest value with `2^(l-1) >= T`.
+pub fn calculate_l(t: u128) -> Result<u8, PixelError> {
+    let mut l: u8 = 1;
+    let mut leaves: u128 = 1;
+    while leaves < t {
+        leaves = leaves
+            .checked_mul(2)
+            .ok_or(PixelError::NotEnoughGenerators { n: l as usize + 2 })?;
+        l += 1;
+    }
+    Ok(l)
+}